@@ -1,21 +1,163 @@
+mod number;
+mod parser;
+
 use std::collections::HashMap;
+use csv::Writer;
 use serde::{Deserialize, Serialize};
 use std::fs;
 use std::error::Error;
 
+use number::{FixedPoint, Number, RationalNumber};
+
 /// Represents an election with its ID, description, and available choices.
 #[derive(Serialize, Deserialize, Debug)]
-struct Election {
-    id: u32,
-    description: String,
-    choices: Vec<Choice>,
+pub(crate) struct Election {
+    pub(crate) id: u32,
+    pub(crate) description: String,
+    pub(crate) choices: Vec<Choice>,
+    /// Number of seats to fill. Single-mark elections fill exactly one seat,
+    /// so this defaults to `1` for backwards compatibility with existing
+    /// election files.
+    #[serde(default = "default_seats")]
+    pub(crate) seats: usize,
+    /// Choice IDs withdrawn before counting: candidates who dropped out
+    /// after ballots were printed. They take no votes and win no seats,
+    /// but remain in `results` so the output distinguishes "ran and lost"
+    /// from "withdrawn".
+    #[serde(default)]
+    pub(crate) withdrawn: Vec<u32>,
+    /// How to resolve a tie in vote counts. Defaults to `FirstListed` so
+    /// existing election files keep tallying deterministically without
+    /// needing to specify anything.
+    #[serde(default = "default_tie_strategy")]
+    pub(crate) tie_strategy: TieStrategy,
+}
+
+fn default_seats() -> usize {
+    1
+}
+
+fn default_tie_strategy() -> TieStrategy {
+    TieStrategy::FirstListed
+}
+
+/// How to resolve a tie in vote counts, at either of the two points a
+/// count can produce one: the FPTP winner, or the STV candidate to
+/// eliminate. The chosen strategy and the IDs it chose between are
+/// recorded on the output via `TieBreak` so a tie's resolution can always
+/// be audited, not just trusted.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "snake_case", tag = "type")]
+pub(crate) enum TieStrategy {
+    /// Prefer whichever tied choice was ahead earliest in the count's
+    /// round history. FPTP has only one round, so this falls back to
+    /// `FirstListed` there, as does an STV tie no round distinguishes.
+    Forward,
+    /// Prefer whichever tied choice was ahead most recently in the
+    /// count's round history, working backward from the current round.
+    /// Falls back the same way `Forward` does.
+    Backward,
+    /// Break the tie with a deterministic pseudo-random draw, seeded so
+    /// the same inputs always resolve the same way.
+    Random { seed: u64 },
+    /// Break the tie by the order choices are listed in the election.
+    FirstListed,
+}
+
+/// A record of a tie that occurred during counting and how it was
+/// resolved, so the resolution is visible in the output rather than
+/// silently baked into a winner or elimination.
+#[derive(Serialize, Debug)]
+struct TieBreak {
+    tied_choice_ids: Vec<u32>,
+    resolved_choice_id: u32,
+    strategy: TieStrategy,
+}
+
+/// Derive a permutation step from a seed. Used by `TieStrategy::Random` so
+/// a tie resolves the same way every time the same seed is used, while
+/// still varying across different ties within one count via `salt`.
+fn splitmix64(x: u64) -> u64 {
+    let x = x.wrapping_add(0x9E3779B97F4A7C15);
+    let mut z = x;
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+    z ^ (z >> 31)
+}
+
+/// Order a set of tied choice IDs from most- to least-preferred per
+/// `strategy`. The caller takes `.first()` as the tie's winner (FPTP) or
+/// `.last()` as the choice to eliminate (STV).
+///
+/// - `choices`: the election's choices, used by `FirstListed`.
+/// - `rounds`: the count's round history so far, used by `Forward` and
+///   `Backward`. Pass an empty slice for FPTP, which has no rounds.
+/// - `salt`: folded into `Random`'s seed so ties in different contests or
+///   rounds of the same count don't all resolve identically.
+fn rank_tied<N: Number>(
+    tied: &[u32],
+    strategy: &TieStrategy,
+    choices: &[Choice],
+    rounds: &[StvRound<N>],
+    salt: u64,
+) -> Vec<u32> {
+    match strategy {
+        TieStrategy::FirstListed => {
+            choices.iter().map(|c| c.id).filter(|id| tied.contains(id)).collect()
+        }
+        TieStrategy::Random { seed } => {
+            let mut order = tied.to_vec();
+            let mut state = seed.wrapping_add(salt);
+            for i in (1..order.len()).rev() {
+                state = splitmix64(state);
+                order.swap(i, (state as usize) % (i + 1));
+            }
+            order
+        }
+        TieStrategy::Forward | TieStrategy::Backward => {
+            let history: Vec<&StvRound<N>> = if matches!(strategy, TieStrategy::Forward) {
+                rounds.iter().collect()
+            } else {
+                rounds.iter().rev().collect()
+            };
+            for round in history {
+                let mut counts: Vec<(u32, &N)> = tied
+                    .iter()
+                    .filter_map(|id| {
+                        round.votes.iter().find(|v| v.choice_id == *id).map(|v| (*id, &v.total_count))
+                    })
+                    .collect();
+                if counts.len() < 2 {
+                    continue;
+                }
+                counts.sort_by(|a, b| b.1.partial_cmp(a.1).unwrap());
+                if counts.first().map(|c| c.1) != counts.last().map(|c| c.1) {
+                    let mut order: Vec<u32> = counts.into_iter().map(|(id, _)| id).collect();
+                    let remainder: Vec<u32> =
+                        tied.iter().copied().filter(|id| !order.contains(id)).collect();
+                    order.extend(remainder);
+                    return order;
+                }
+            }
+            rank_tied(tied, &TieStrategy::FirstListed, choices, rounds, salt)
+        }
+    }
+}
+
+/// A single ballot file's worth of contests, tallied together in one pass.
+/// Real ballot files usually cover several simultaneous contests (council,
+/// referendum, board seats), routed from one `votes.json` (single-seat
+/// contests) and one `ranked_votes.json` (multi-seat contests).
+#[derive(Serialize, Deserialize, Debug)]
+struct VotePlan {
+    elections: Vec<Election>,
 }
 
 /// Represents a single choice in an election.
 #[derive(Serialize, Deserialize, Debug, Clone)]
-struct Choice {
-    id: u32,
-    text: String,
+pub(crate) struct Choice {
+    pub(crate) id: u32,
+    pub(crate) text: String,
 }
 
 /// Represents a vote with a contest ID and a choice ID.
@@ -25,20 +167,43 @@ struct Vote {
     choice_id: u32,
 }
 
+/// Represents a ranked-choice ballot: an ordered list of choice IDs from
+/// most to least preferred, used for single transferable vote counting.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub(crate) struct RankedVote {
+    pub(crate) contest_id: u32,
+    pub(crate) preferences: Vec<u32>,
+}
+
 /// Represents the results of an election tally.
 #[derive(Serialize, Debug)]
-struct ResultData {
+#[serde(bound = "N: Number")]
+struct ResultData<N: Number> {
     contest_id: u32,
     total_votes: u32,
-    results: Vec<ChoiceResult>,
+    results: Vec<ChoiceResult<N>>,
     winner: Option<Choice>,
+    tie_break: Option<TieBreak>,
+}
+
+/// Whether a choice was standing at count time or withdrawn beforehand.
+/// Kept distinct from a standing choice that simply received zero votes.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+enum ChoiceState {
+    Standing,
+    Withdrawn,
 }
 
-/// Represents the tally of votes for a specific choice.
+/// Represents the tally of votes for a specific choice. Generic over the
+/// `Number` backend so callers can choose exact-rational or fixed-point
+/// arithmetic for the count.
 #[derive(Serialize, Debug)]
-struct ChoiceResult {
+#[serde(bound = "N: Number")]
+struct ChoiceResult<N: Number> {
     choice_id: u32,
-    total_count: u32,
+    total_count: N,
+    state: ChoiceState,
 }
 
 /// Tally the votes for a given election, returning the results.
@@ -47,37 +212,55 @@ struct ChoiceResult {
 /// - `votes`: The list of votes to be tallied.
 ///
 /// Returns a `ResultData` containing the results and the winner.
-fn tally_votes(election: &Election, votes: &[Vote]) -> ResultData {
+fn tally_votes<N: Number>(election: &Election, votes: &[Vote]) -> ResultData<N> {
     let mut vote_counts: HashMap<u32, u32> = HashMap::new();
 
-    // Filter votes to only include those matching the election ID
+    // Filter votes to only include those matching the election ID. Marks
+    // for a withdrawn choice are ignored, as if the ballot never named it.
     for vote in votes.iter().filter(|v| v.contest_id == election.id) {
-        if election.choices.iter().any(|c| c.id == vote.choice_id) {
+        if election.choices.iter().any(|c| c.id == vote.choice_id)
+            && !election.withdrawn.contains(&vote.choice_id)
+        {
             *vote_counts.entry(vote.choice_id).or_insert(0) += 1;
         }
     }
 
     let total_votes = vote_counts.values().sum();
 
-    let mut results: Vec<ChoiceResult> = election.choices.iter().map(|choice| {
+    let mut results: Vec<ChoiceResult<N>> = election.choices.iter().map(|choice| {
+        let withdrawn = election.withdrawn.contains(&choice.id);
+        let count = if withdrawn { 0 } else { *vote_counts.get(&choice.id).unwrap_or(&0) };
         ChoiceResult {
             choice_id: choice.id,
-            total_count: *vote_counts.get(&choice.id).unwrap_or(&0),
+            total_count: N::parse(&count.to_string()).expect("integer counts always parse"),
+            state: if withdrawn { ChoiceState::Withdrawn } else { ChoiceState::Standing },
         }
     }).collect();
 
-    results.sort_by(|a, b| b.total_count.cmp(&a.total_count));
+    results.sort_by(|a, b| b.total_count.partial_cmp(&a.total_count).unwrap());
 
-    let winner = if results.len() > 1 && results[0].total_count == results[1].total_count {
-        None // Tie case: No winner
-    } else {
-        results.first().and_then(|r| {
-            if r.total_count > 0 {
-                election.choices.iter().find(|c| c.id == r.choice_id).cloned()
+    let mut tie_break = None;
+    let winner = match results.first() {
+        Some(top) if top.total_count > N::zero() => {
+            let tied: Vec<u32> = results
+                .iter()
+                .filter(|r| r.total_count == top.total_count)
+                .map(|r| r.choice_id)
+                .collect();
+            let winner_id = if tied.len() > 1 {
+                let order = rank_tied::<N>(&tied, &election.tie_strategy, &election.choices, &[], election.id as u64);
+                tie_break = Some(TieBreak {
+                    tied_choice_ids: tied,
+                    resolved_choice_id: order[0],
+                    strategy: election.tie_strategy.clone(),
+                });
+                order[0]
             } else {
-                None
-            }
-        })
+                tied[0]
+            };
+            election.choices.iter().find(|c| c.id == winner_id).cloned()
+        }
+        _ => None,
     };
 
     ResultData {
@@ -85,30 +268,591 @@ fn tally_votes(election: &Election, votes: &[Vote]) -> ResultData {
         total_votes,
         results,
         winner,
+        tie_break,
     }
 }
 
-/// Main function to read input files, tally votes, and write the results to an output file.
-fn main() -> Result<(), Box<dyn Error>> {
-    let election_data = fs::read_to_string("election.json")?;
-    let votes_data = fs::read_to_string("votes.json")?;
+/// A contest's outcome distilled to the headline numbers, independent of
+/// which tally method or `Number` backend produced it.
+#[derive(Serialize, Debug)]
+struct ContestSummary {
+    contest_id: u32,
+    valid_ballots: u32,
+    invalid_ballots: u32,
+    winner: Option<Choice>,
+}
+
+/// A top-level summary of every contest in a `VotePlan`, so the outcome of
+/// a whole ballot file can be read at a glance.
+#[derive(Serialize, Debug)]
+struct PlanSummary {
+    contests: Vec<ContestSummary>,
+}
 
-    let election: Election = serde_json::from_str(&election_data)?;
-    let votes: Vec<Vote> = votes_data.lines().map(|line| serde_json::from_str(line).unwrap()).collect();
+/// The full output of tallying a `VotePlan`: one `ResultData` per
+/// single-seat contest, one `StvResultData` per multi-seat contest, plus
+/// the plan-wide summary (which covers only the single-seat contests,
+/// since "winner" and "invalid ballots" aren't well-defined for STV).
+#[derive(Serialize, Debug)]
+#[serde(bound = "N: Number")]
+struct PlanResults<N: Number> {
+    results: Vec<ResultData<N>>,
+    stv_results: Vec<StvResultData<N>>,
+    summary: PlanSummary,
+}
 
-    let result = tally_votes(&election, &votes);
+/// Tally every contest in a `VotePlan`, routing each by its `seats`: a
+/// single-seat contest is tallied FPTP against `votes`, a multi-seat
+/// contest is tallied STV against `ranked_votes`. Votes and ranked votes
+/// are each routed within their tally to the election matching their
+/// `contest_id`.
+///
+/// - `plan`: The elections to tally.
+/// - `votes`: The single-mark votes cast across the plan's single-seat
+///   contests.
+/// - `ranked_votes`: The ranked ballots cast across the plan's
+///   multi-seat contests.
+///
+/// Returns a `PlanResults` containing one result per contest and a
+/// plan-wide summary of the single-seat contests.
+fn tally_plan<N: Number>(plan: &VotePlan, votes: &[Vote], ranked_votes: &[RankedVote]) -> PlanResults<N> {
+    let (fptp_elections, stv_elections): (Vec<&Election>, Vec<&Election>) =
+        plan.elections.iter().partition(|election| election.seats <= 1);
 
-    let result_json = serde_json::to_string_pretty(&result)?;
-    fs::write("result.json", result_json)?;
+    let results: Vec<ResultData<N>> =
+        fptp_elections.iter().map(|election| tally_votes(election, votes)).collect();
 
-    println!("Tallying completed. Results written to result.json.");
+    let contests = fptp_elections
+        .iter()
+        .zip(results.iter())
+        .map(|(election, result)| {
+            let cast_for_contest = votes.iter().filter(|v| v.contest_id == election.id).count() as u32;
+            ContestSummary {
+                contest_id: election.id,
+                valid_ballots: result.total_votes,
+                invalid_ballots: cast_for_contest.saturating_sub(result.total_votes),
+                winner: result.winner.clone(),
+            }
+        })
+        .collect();
+
+    let stv_results: Vec<StvResultData<N>> =
+        stv_elections.iter().map(|election| tally_stv(election, ranked_votes)).collect();
+
+    PlanResults { results, stv_results, summary: PlanSummary { contests } }
+}
+
+/// One transfer of vote value from an elected or eliminated choice to
+/// another continuing choice, recorded for audit purposes.
+#[derive(Serialize, Debug)]
+#[serde(bound = "N: Number")]
+struct Transfer<N: Number> {
+    from_choice_id: u32,
+    to_choice_id: u32,
+    amount: N,
+}
+
+/// A single round of an STV count: the running tally for every continuing
+/// choice plus whatever happened at the end of the round (an election, an
+/// elimination, and the resulting transfers).
+#[derive(Serialize, Debug)]
+#[serde(bound = "N: Number")]
+struct StvRound<N: Number> {
+    round: usize,
+    votes: Vec<StvChoiceCount<N>>,
+    elected: Vec<u32>,
+    eliminated: Option<u32>,
+    transfers: Vec<Transfer<N>>,
+    tie_break: Option<TieBreak>,
+}
+
+/// The vote value held by a choice at a point in the count.
+#[derive(Serialize, Debug)]
+#[serde(bound = "N: Number")]
+struct StvChoiceCount<N: Number> {
+    choice_id: u32,
+    total_count: N,
+}
+
+/// Represents the results of an STV count: the elected choices, the quota
+/// used to elect them, and a per-round log so the count can be audited.
+#[derive(Serialize, Debug)]
+#[serde(bound = "N: Number")]
+struct StvResultData<N: Number> {
+    contest_id: u32,
+    seats: usize,
+    quota: N,
+    total_valid_ballots: u32,
+    elected: Vec<Choice>,
+    rounds: Vec<StvRound<N>>,
+}
+
+/// A ranked ballot as tracked during the count: the preferences still to be
+/// considered, and the vote value it currently carries.
+struct StvBallot<N: Number> {
+    preferences: Vec<u32>,
+    value: N,
+}
+
+impl<N: Number> StvBallot<N> {
+    /// The first preference that is still in the continuing set, if any.
+    fn current_choice(&self, continuing: &[u32]) -> Option<u32> {
+        self.preferences.iter().find(|p| continuing.contains(p)).copied()
+    }
+}
+
+/// Tally a ranked-ballot contest using single transferable vote (STV) with
+/// a Droop quota and Gregory surplus transfers.
+///
+/// - `election`: The election to tally, including the number of seats.
+/// - `votes`: The ranked ballots cast for this election's contest.
+///
+/// Returns an `StvResultData` containing the elected choices and a log of
+/// every round of the count.
+fn tally_stv<N: Number>(election: &Election, votes: &[RankedVote]) -> StvResultData<N> {
+    let one = N::parse("1").expect("\"1\" always parses");
+
+    let mut ballots: Vec<StvBallot<N>> = votes
+        .iter()
+        .filter(|v| v.contest_id == election.id)
+        .map(|v| StvBallot {
+            preferences: v
+                .preferences
+                .iter()
+                .copied()
+                .filter(|p| election.choices.iter().any(|c| c.id == *p) && !election.withdrawn.contains(p))
+                .collect(),
+            value: one.clone(),
+        })
+        .collect();
+
+    let total_valid_ballots = ballots.len() as u32;
+    let quota_int = total_valid_ballots as usize / (election.seats + 1) + 1;
+    let quota = N::parse(&quota_int.to_string()).expect("integer quota always parses");
+
+    let mut elected: Vec<u32> = Vec::new();
+    let mut eliminated: Vec<u32> = Vec::new();
+    let mut rounds: Vec<StvRound<N>> = Vec::new();
+
+    loop {
+        let continuing: Vec<u32> = election
+            .choices
+            .iter()
+            .map(|c| c.id)
+            .filter(|id| !elected.contains(id) && !eliminated.contains(id) && !election.withdrawn.contains(id))
+            .collect();
+
+        let remaining_seats = election.seats.saturating_sub(elected.len());
+        if remaining_seats == 0 {
+            break;
+        }
+
+        let mut tally: HashMap<u32, N> = continuing.iter().map(|id| (*id, N::zero())).collect();
+        for ballot in ballots.iter() {
+            if let Some(choice_id) = ballot.current_choice(&continuing) {
+                let entry = tally.entry(choice_id).or_insert_with(N::zero);
+                *entry = entry.clone() + ballot.value.clone();
+            }
+        }
+
+        let mut votes_this_round: Vec<StvChoiceCount<N>> = continuing
+            .iter()
+            .map(|id| StvChoiceCount { choice_id: *id, total_count: tally.get(id).cloned().unwrap_or_else(N::zero) })
+            .collect();
+        votes_this_round.sort_by(|a, b| b.total_count.partial_cmp(&a.total_count).unwrap());
+
+        if continuing.len() <= remaining_seats {
+            // Remaining seats are filled by whoever is still standing,
+            // whether or not they reached quota. Log this as a round too,
+            // even though no one was eliminated, since it's the decisive
+            // step whenever the field narrows to the number of open seats.
+            rounds.push(StvRound {
+                round: rounds.len() + 1,
+                votes: votes_this_round,
+                elected: continuing.clone(),
+                eliminated: None,
+                transfers: Vec::new(),
+                tie_break: None,
+            });
+            elected.extend(continuing);
+            break;
+        }
+
+        let mut round_elected: Vec<u32> = Vec::new();
+        let mut round_eliminated: Option<u32> = None;
+        let mut round_tie_break: Option<TieBreak> = None;
+        let mut transfers: Vec<Transfer<N>> = Vec::new();
+
+        let reaching_quota: Vec<u32> = votes_this_round
+            .iter()
+            .filter(|c| c.total_count >= quota)
+            .map(|c| c.choice_id)
+            .collect();
+
+        if !reaching_quota.is_empty() {
+            for choice_id in reaching_quota {
+                let candidate_total = tally.get(&choice_id).cloned().unwrap_or_else(N::zero);
+                let surplus = candidate_total.clone() - quota.clone();
+                elected.push(choice_id);
+                round_elected.push(choice_id);
+
+                if surplus > N::zero() {
+                    let fraction = surplus / candidate_total;
+                    let next_continuing: Vec<u32> = election
+                        .choices
+                        .iter()
+                        .map(|c| c.id)
+                        .filter(|id| !elected.contains(id) && !eliminated.contains(id) && !election.withdrawn.contains(id))
+                        .collect();
+
+                    let mut transferred: HashMap<u32, N> = HashMap::new();
+                    for ballot in ballots.iter_mut() {
+                        if ballot.current_choice(&continuing) == Some(choice_id) {
+                            let moved = ballot.value.clone() * fraction.clone();
+                            ballot.value = moved.clone();
+                            if let Some(next) = ballot.current_choice(&next_continuing) {
+                                let entry = transferred.entry(next).or_insert_with(N::zero);
+                                *entry = entry.clone() + moved;
+                            }
+                        }
+                    }
+                    for (to_choice_id, amount) in transferred {
+                        transfers.push(Transfer { from_choice_id: choice_id, to_choice_id, amount });
+                    }
+                }
+            }
+        } else {
+            let lowest = votes_this_round.last().map(|c| {
+                let tied: Vec<u32> = votes_this_round
+                    .iter()
+                    .filter(|c2| c2.total_count == c.total_count)
+                    .map(|c2| c2.choice_id)
+                    .collect();
+                if tied.len() > 1 {
+                    let order = rank_tied(&tied, &election.tie_strategy, &election.choices, &rounds, election.id as u64 + rounds.len() as u64);
+                    let eliminated_id = *order.last().unwrap();
+                    round_tie_break = Some(TieBreak {
+                        tied_choice_ids: tied,
+                        resolved_choice_id: eliminated_id,
+                        strategy: election.tie_strategy.clone(),
+                    });
+                    eliminated_id
+                } else {
+                    tied[0]
+                }
+            });
+            if let Some(choice_id) = lowest {
+                eliminated.push(choice_id);
+                round_eliminated = Some(choice_id);
+
+                let next_continuing: Vec<u32> = election
+                    .choices
+                    .iter()
+                    .map(|c| c.id)
+                    .filter(|id| !elected.contains(id) && !eliminated.contains(id) && !election.withdrawn.contains(id))
+                    .collect();
+
+                let mut transferred: HashMap<u32, N> = HashMap::new();
+                for ballot in ballots.iter() {
+                    if ballot.current_choice(&continuing) == Some(choice_id) {
+                        if let Some(next) = ballot.current_choice(&next_continuing) {
+                            let entry = transferred.entry(next).or_insert_with(N::zero);
+                            *entry = entry.clone() + ballot.value.clone();
+                        }
+                    }
+                }
+                for (to_choice_id, amount) in transferred {
+                    transfers.push(Transfer { from_choice_id: choice_id, to_choice_id, amount });
+                }
+            } else {
+                break;
+            }
+        }
+
+        rounds.push(StvRound {
+            round: rounds.len() + 1,
+            votes: votes_this_round,
+            elected: round_elected,
+            eliminated: round_eliminated,
+            transfers,
+            tie_break: round_tie_break,
+        });
+    }
+
+    let elected_choices = elected
+        .iter()
+        .filter_map(|id| election.choices.iter().find(|c| c.id == *id).cloned())
+        .collect();
+
+    StvResultData {
+        contest_id: election.id,
+        seats: election.seats,
+        quota,
+        total_valid_ballots,
+        elected: elected_choices,
+        rounds,
+    }
+}
+
+/// The file format results are written in, chosen by the `--format` flag.
+#[derive(Debug, Clone, PartialEq)]
+enum OutputFormat {
+    Json,
+    Csv,
+}
+
+impl OutputFormat {
+    fn file_name(&self) -> &'static str {
+        match self {
+            OutputFormat::Json => "result.json",
+            OutputFormat::Csv => "result.csv",
+        }
+    }
+}
+
+/// Parse the `--ballots <path>` CLI flag: a BLT ballot file to tally
+/// instead of `election.json`/`votes.json`/`ranked_votes.json`, for
+/// ingesting ballots produced by other STV tools.
+fn parse_ballots_path(args: &[String]) -> Result<Option<&str>, Box<dyn Error>> {
+    match args.iter().position(|a| a == "--ballots") {
+        Some(i) => match args.get(i + 1) {
+            Some(path) => Ok(Some(path.as_str())),
+            None => Err("--ballots requires a file path".into()),
+        },
+        None => Ok(None),
+    }
+}
+
+/// Parse the `--export-ballots <path>` CLI flag: write the first election's
+/// ranked ballots back out as a BLT file, for converting the current JSON
+/// input into a form other STV tools can read.
+fn parse_export_ballots_path(args: &[String]) -> Result<Option<&str>, Box<dyn Error>> {
+    match args.iter().position(|a| a == "--export-ballots") {
+        Some(i) => match args.get(i + 1) {
+            Some(path) => Ok(Some(path.as_str())),
+            None => Err("--export-ballots requires a file path".into()),
+        },
+        None => Ok(None),
+    }
+}
+
+/// Parse the `--format <json|csv>` CLI flag, defaulting to `Json` when
+/// it's absent.
+fn parse_output_format(args: &[String]) -> Result<OutputFormat, Box<dyn Error>> {
+    match args.iter().position(|a| a == "--format") {
+        Some(i) => match args.get(i + 1).map(String::as_str) {
+            Some("json") => Ok(OutputFormat::Json),
+            Some("csv") => Ok(OutputFormat::Csv),
+            Some(other) => {
+                Err(format!("unknown output format {other:?}, expected \"json\" or \"csv\"").into())
+            }
+            None => Err("--format requires a value (json or csv)".into()),
+        },
+        None => Ok(OutputFormat::Json),
+    }
+}
+
+/// Render a `PlanResults` as CSV: one row per `ChoiceResult`, with columns
+/// `contest_id, choice_id, choice_text, total_count`, plus a trailing
+/// winner row per contest. `plan` supplies the choice text, which
+/// `ChoiceResult` doesn't carry. Only the single-seat contests in
+/// `results.results` are rendered; `stv_results` are not included.
+fn render_csv<N: Number>(plan: &VotePlan, results: &PlanResults<N>) -> Result<Vec<u8>, Box<dyn Error>> {
+    let mut writer = Writer::from_writer(Vec::new());
+    writer.write_record(["contest_id", "choice_id", "choice_text", "total_count"])?;
+
+    for result in &results.results {
+        let election = plan
+            .elections
+            .iter()
+            .find(|e| e.id == result.contest_id)
+            .ok_or_else(|| format!("no election found for contest_id {}", result.contest_id))?;
+        for choice_result in &result.results {
+            let choice_text = election
+                .choices
+                .iter()
+                .find(|c| c.id == choice_result.choice_id)
+                .map(|c| c.text.as_str())
+                .unwrap_or("");
+            writer.write_record([
+                result.contest_id.to_string(),
+                choice_result.choice_id.to_string(),
+                choice_text.to_string(),
+                choice_result.total_count.to_string(),
+            ])?;
+        }
+
+        match &result.winner {
+            Some(winner) => {
+                let total_count = result
+                    .results
+                    .iter()
+                    .find(|r| r.choice_id == winner.id)
+                    .map(|r| r.total_count.to_string())
+                    .unwrap_or_default();
+                writer.write_record([
+                    result.contest_id.to_string(),
+                    winner.id.to_string(),
+                    winner.text.clone(),
+                    total_count,
+                ])?;
+            }
+            None => {
+                writer.write_record([result.contest_id.to_string(), String::new(), String::new(), String::new()])?;
+            }
+        }
+    }
+
+    writer.flush()?;
+    Ok(writer.into_inner()?)
+}
+
+/// Write a `PlanResults` to disk in the requested `format`.
+fn write_results<N: Number>(
+    plan: &VotePlan,
+    results: &PlanResults<N>,
+    format: &OutputFormat,
+) -> Result<(), Box<dyn Error>> {
+    match format {
+        OutputFormat::Json => fs::write(format.file_name(), serde_json::to_string_pretty(results)?)?,
+        OutputFormat::Csv => fs::write(format.file_name(), render_csv(plan, results)?)?,
+    }
+    Ok(())
+}
+
+/// Which exact-arithmetic `Number` backend to tally with, chosen by the
+/// `--number-backend` flag. `N` is a compile-time type parameter on every
+/// tallying function, so this is resolved to a concrete `run::<N>` call in
+/// `main` rather than threaded through as a runtime value.
+enum NumberBackend {
+    /// Arbitrary-precision rationals: exact, no rounding at any step.
+    Rational,
+    /// Fixed-point with the given number of decimal places, rounding at
+    /// each transfer step the way some jurisdictions' rules mandate.
+    Fixed(u32),
+}
+
+/// Parse the `--number-backend <rational|fixed:N>` CLI flag, defaulting
+/// to `Rational` when it's absent.
+fn parse_number_backend(args: &[String]) -> Result<NumberBackend, Box<dyn Error>> {
+    match args.iter().position(|a| a == "--number-backend") {
+        Some(i) => match args.get(i + 1).map(String::as_str) {
+            Some("rational") => Ok(NumberBackend::Rational),
+            Some(spec) => {
+                let decimals = spec
+                    .strip_prefix("fixed:")
+                    .ok_or_else(|| {
+                        format!("unknown number backend {spec:?}, expected \"rational\" or \"fixed:<decimals>\"")
+                    })?
+                    .parse::<u32>()
+                    .map_err(|e| format!("invalid fixed-point decimals in {spec:?}: {e}"))?;
+                Ok(NumberBackend::Fixed(decimals))
+            }
+            None => Err("--number-backend requires a value (rational or fixed:<decimals>)".into()),
+        },
+        None => Ok(NumberBackend::Rational),
+    }
+}
+
+/// The inputs `run` needs to tally a contest, however they were read in.
+type TallyInputs = (VotePlan, Vec<Vote>, Vec<RankedVote>);
+
+/// Read a BLT ballot file into the same `VotePlan`/`Vote`/`RankedVote`
+/// shapes `run` otherwise reads from `election.json` and friends, so a BLT
+/// file can be tallied without first converting it by hand. The contest ID
+/// is fixed at `1` since a BLT file describes exactly one contest.
+fn read_ballots_file<N: Number>(path: &str) -> Result<TallyInputs, Box<dyn Error>> {
+    let data = fs::read_to_string(path)?;
+    let parsed = parser::parse_blt::<N>(&data, 1)?;
+    let ranked_votes = parser::ranked_votes(parsed.election.id, &parsed.ballots);
+    let plan = VotePlan { elections: vec![parsed.election] };
+    Ok((plan, Vec::new(), ranked_votes))
+}
+
+/// Write `plan`'s first election and `ranked_votes` back out as a BLT file
+/// at `path`, for converting JSON input into a form other STV tools read.
+/// Ballot weight isn't tracked by `RankedVote`, so every exported ballot
+/// gets a weight of `1`.
+fn export_ballots<N: Number>(plan: &VotePlan, ranked_votes: &[RankedVote], path: &str) -> Result<(), Box<dyn Error>> {
+    let election = plan.elections.first().ok_or("no elections to export as BLT")?;
+    let ballots: Vec<parser::BltBallot<N>> = ranked_votes
+        .iter()
+        .filter(|v| v.contest_id == election.id)
+        .map(|v| parser::BltBallot { weight: N::parse("1").unwrap(), preferences: v.preferences.clone() })
+        .collect();
+    fs::write(path, parser::write_blt(election, &ballots))?;
+    Ok(())
+}
+
+/// Read the election plan and ballots, tally them with `N`, and write the
+/// results in `format`. Generic so `main` can monomorphize it once per
+/// `NumberBackend`. Reads a BLT file when `ballots_path` is given, or the
+/// usual `election.json`/`votes.json`/`ranked_votes.json` trio otherwise.
+/// Also exports the ranked ballots to `export_ballots_path` as BLT, if set.
+fn run<N: Number>(
+    format: &OutputFormat,
+    ballots_path: Option<&str>,
+    export_ballots_path: Option<&str>,
+) -> Result<(), Box<dyn Error>> {
+    let (plan, votes, ranked_votes) = match ballots_path {
+        Some(path) => read_ballots_file::<N>(path)?,
+        None => {
+            let election_data = fs::read_to_string("election.json")?;
+            let plan: VotePlan = serde_json::from_str(&election_data)?;
+
+            let votes: Vec<Vote> = match fs::read_to_string("votes.json") {
+                Ok(data) => data.lines().map(|line| serde_json::from_str(line).unwrap()).collect(),
+                Err(_) => Vec::new(),
+            };
+            // Ranked ballots for the plan's multi-seat (`seats > 1`) contests,
+            // read from a separate file since they carry a preference list
+            // rather than a single `choice_id`. Omitted entirely when the
+            // plan has none.
+            let ranked_votes: Vec<RankedVote> = match fs::read_to_string("ranked_votes.json") {
+                Ok(data) => data.lines().map(|line| serde_json::from_str(line).unwrap()).collect(),
+                Err(_) => Vec::new(),
+            };
+            (plan, votes, ranked_votes)
+        }
+    };
+
+    if let Some(path) = export_ballots_path {
+        export_ballots::<N>(&plan, &ranked_votes, path)?;
+    }
+
+    let result = tally_plan::<N>(&plan, &votes, &ranked_votes);
+    write_results(&plan, &result, format)?;
+
+    println!("Tallying completed. Results written to {}.", format.file_name());
 
     Ok(())
 }
 
+fn main() -> Result<(), Box<dyn Error>> {
+    let args: Vec<String> = std::env::args().collect();
+    let format = parse_output_format(&args)?;
+    let backend = parse_number_backend(&args)?;
+    let ballots_path = parse_ballots_path(&args)?;
+    let export_ballots_path = parse_export_ballots_path(&args)?;
+
+    match backend {
+        NumberBackend::Rational => run::<RationalNumber>(&format, ballots_path, export_ballots_path),
+        NumberBackend::Fixed(0) => run::<FixedPoint<0>>(&format, ballots_path, export_ballots_path),
+        NumberBackend::Fixed(1) => run::<FixedPoint<1>>(&format, ballots_path, export_ballots_path),
+        NumberBackend::Fixed(2) => run::<FixedPoint<2>>(&format, ballots_path, export_ballots_path),
+        NumberBackend::Fixed(3) => run::<FixedPoint<3>>(&format, ballots_path, export_ballots_path),
+        NumberBackend::Fixed(4) => run::<FixedPoint<4>>(&format, ballots_path, export_ballots_path),
+        NumberBackend::Fixed(6) => run::<FixedPoint<6>>(&format, ballots_path, export_ballots_path),
+        NumberBackend::Fixed(other) => {
+            Err(format!("unsupported fixed-point decimals {other}; supported: 0-4, 6").into())
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use number::FixedPoint;
 
     /// Test 01: No Choices
     #[test]
@@ -117,10 +861,13 @@ mod tests {
             id: 1,
             description: "Empty Election".to_string(),
             choices: vec![],
+            seats: 1,
+            withdrawn: vec![],
+            tie_strategy: TieStrategy::FirstListed,
         };
 
         let votes = vec![Vote { contest_id: 1, choice_id: 1 }];
-        let result = tally_votes(&election, &votes);
+        let result = tally_votes::<RationalNumber>(&election, &votes);
 
         println!(
             "\nTest: No Choices\nInput Election: {}\nInput Votes: {}\nExpected Total Votes: 0\nActual: {}\nResult: {}\n",
@@ -145,25 +892,31 @@ mod tests {
                 Choice { id: 1, text: "Option A".to_string() },
                 Choice { id: 2, text: "Option B".to_string() },
             ],
+            seats: 1,
+            withdrawn: vec![],
+            tie_strategy: TieStrategy::FirstListed,
         };
 
         let votes = vec![
             Vote { contest_id: 1, choice_id: 1 },
             Vote { contest_id: 1, choice_id: 2 },
         ];
-        let result = tally_votes(&election, &votes);
+        let result = tally_votes::<RationalNumber>(&election, &votes);
 
         println!(
             "\nTest: Tied Votes\nInput Election: {}\nInput Votes: {}\nExpected Total Votes: 2\nActual: {}\nResult: {}\n",
             serde_json::to_string_pretty(&election).unwrap(),
             serde_json::to_string_pretty(&votes).unwrap(),
             serde_json::to_string_pretty(&result).unwrap(),
-            if result.total_votes == 2 && result.winner.is_none() { "PASSED" } else { "FAILED" }
+            if result.total_votes == 2 && result.tie_break.is_some() { "PASSED" } else { "FAILED" }
         );
 
         assert_eq!(result.total_votes, 2);
         assert_eq!(result.results.len(), 2);
-        assert!(result.winner.is_none());
+        // The election's tie_strategy resolves this rather than leaving it
+        // winnerless; see test_12 and test_13 for the strategies in depth.
+        assert_eq!(result.winner.unwrap().id, 1);
+        assert!(result.tie_break.is_some());
     }
 
     /// Test 03: Invalid Votes
@@ -175,21 +928,24 @@ mod tests {
             choices: vec![
                 Choice { id: 1, text: "Valid Option".to_string() },
             ],
+            seats: 1,
+            withdrawn: vec![],
+            tie_strategy: TieStrategy::FirstListed,
         };
 
         let votes = vec![Vote { contest_id: 1, choice_id: 99 }];
-        let result = tally_votes(&election, &votes);
+        let result = tally_votes::<RationalNumber>(&election, &votes);
 
         println!(
             "\nTest: Invalid Votes\nInput Election: {}\nInput Votes: {}\nExpected Total Votes: 0\nActual: {}\nResult: {}\n",
             serde_json::to_string_pretty(&election).unwrap(),
             serde_json::to_string_pretty(&votes).unwrap(),
             serde_json::to_string_pretty(&result).unwrap(),
-            if result.total_votes == 0 && result.results[0].total_count == 0 { "PASSED" } else { "FAILED" }
+            if result.total_votes == 0 && result.results[0].total_count == RationalNumber::zero() { "PASSED" } else { "FAILED" }
         );
 
         assert_eq!(result.total_votes, 0);
-        assert_eq!(result.results[0].total_count, 0);
+        assert_eq!(result.results[0].total_count, RationalNumber::zero());
         assert!(result.winner.is_none());
     }
 
@@ -202,21 +958,24 @@ mod tests {
             choices: vec![
                 Choice { id: 1, text: "Option A".to_string() },
             ],
+            seats: 1,
+            withdrawn: vec![],
+            tie_strategy: TieStrategy::FirstListed,
         };
 
         let votes = vec![Vote { contest_id: 2, choice_id: 1 }];
-        let result = tally_votes(&election, &votes);
+        let result = tally_votes::<RationalNumber>(&election, &votes);
 
         println!(
             "\nTest: Multiple Contests\nInput Election: {}\nInput Votes: {}\nExpected Total Votes: 0\nActual: {}\nResult: {}\n",
             serde_json::to_string_pretty(&election).unwrap(),
             serde_json::to_string_pretty(&votes).unwrap(),
             serde_json::to_string_pretty(&result).unwrap(),
-            if result.total_votes == 0 && result.results.iter().all(|r| r.total_count == 0) { "PASSED" } else { "FAILED" }
+            if result.total_votes == 0 && result.results.iter().all(|r| r.total_count == RationalNumber::zero()) { "PASSED" } else { "FAILED" }
         );
 
         assert_eq!(result.total_votes, 0);
-        assert!(result.results.iter().all(|r| r.total_count == 0));
+        assert!(result.results.iter().all(|r| r.total_count == RationalNumber::zero()));
         assert!(result.winner.is_none());
     }
 
@@ -235,4 +994,525 @@ mod tests {
 
         assert!(parsed_result.is_err(), "Expected an error when parsing incomplete JSON.");
     }
+
+    /// Test 06: STV Single Seat Majority
+    #[test]
+    fn test_06_stv_single_seat_majority() {
+        let election = Election {
+            id: 1,
+            description: "Single Seat".to_string(),
+            choices: vec![
+                Choice { id: 1, text: "Option A".to_string() },
+                Choice { id: 2, text: "Option B".to_string() },
+            ],
+            seats: 1,
+            withdrawn: vec![],
+            tie_strategy: TieStrategy::FirstListed,
+        };
+
+        let votes = vec![
+            RankedVote { contest_id: 1, preferences: vec![1, 2] },
+            RankedVote { contest_id: 1, preferences: vec![1, 2] },
+            RankedVote { contest_id: 1, preferences: vec![2, 1] },
+        ];
+        let result = tally_stv::<RationalNumber>(&election, &votes);
+
+        println!(
+            "\nTest: STV Single Seat Majority\nInput Election: {}\nInput Votes: {}\nExpected Quota: 2\nResult: {}\n",
+            serde_json::to_string_pretty(&election).unwrap(),
+            serde_json::to_string_pretty(&votes).unwrap(),
+            serde_json::to_string_pretty(&result).unwrap(),
+        );
+
+        assert_eq!(result.quota, RationalNumber::parse("2").unwrap());
+        assert_eq!(result.elected.len(), 1);
+        assert_eq!(result.elected[0].id, 1);
+    }
+
+    /// Test 07: STV Surplus Transfer Fills Two Seats
+    #[test]
+    fn test_07_stv_surplus_transfer() {
+        let election = Election {
+            id: 1,
+            description: "Two Seats".to_string(),
+            choices: vec![
+                Choice { id: 1, text: "Option A".to_string() },
+                Choice { id: 2, text: "Option B".to_string() },
+                Choice { id: 3, text: "Option C".to_string() },
+            ],
+            seats: 2,
+            withdrawn: vec![],
+            tie_strategy: TieStrategy::FirstListed,
+        };
+
+        // Quota = floor(6/3) + 1 = 3. Choice 1 starts above quota and its
+        // surplus should transfer to choice 2, electing both.
+        let votes = vec![
+            RankedVote { contest_id: 1, preferences: vec![1, 2] },
+            RankedVote { contest_id: 1, preferences: vec![1, 2] },
+            RankedVote { contest_id: 1, preferences: vec![1, 2] },
+            RankedVote { contest_id: 1, preferences: vec![1, 2] },
+            RankedVote { contest_id: 1, preferences: vec![2, 1] },
+            RankedVote { contest_id: 1, preferences: vec![3, 1] },
+        ];
+        let result = tally_stv::<RationalNumber>(&election, &votes);
+
+        println!(
+            "\nTest: STV Surplus Transfer\nInput Election: {}\nInput Votes: {}\nResult: {}\n",
+            serde_json::to_string_pretty(&election).unwrap(),
+            serde_json::to_string_pretty(&votes).unwrap(),
+            serde_json::to_string_pretty(&result).unwrap(),
+        );
+
+        assert_eq!(result.quota, RationalNumber::parse("3").unwrap());
+        assert_eq!(result.elected.len(), 2);
+        assert!(result.elected.iter().any(|c| c.id == 1));
+        assert!(result.elected.iter().any(|c| c.id == 2));
+        assert!(!result.rounds[0].transfers.is_empty());
+    }
+
+    /// Test 08: STV With Fixed-Point Arithmetic
+    ///
+    /// Same contest as Test 06, but tallied with a `FixedPoint<2>` backend
+    /// to confirm `tally_stv` is genuinely generic over `Number`.
+    #[test]
+    fn test_08_stv_fixed_point_backend() {
+        let election = Election {
+            id: 1,
+            description: "Single Seat".to_string(),
+            choices: vec![
+                Choice { id: 1, text: "Option A".to_string() },
+                Choice { id: 2, text: "Option B".to_string() },
+            ],
+            seats: 1,
+            withdrawn: vec![],
+            tie_strategy: TieStrategy::FirstListed,
+        };
+
+        let votes = vec![
+            RankedVote { contest_id: 1, preferences: vec![1, 2] },
+            RankedVote { contest_id: 1, preferences: vec![1, 2] },
+            RankedVote { contest_id: 1, preferences: vec![2, 1] },
+        ];
+        let result = tally_stv::<FixedPoint<2>>(&election, &votes);
+
+        assert_eq!(result.quota, FixedPoint::<2>::parse("2").unwrap());
+        assert_eq!(result.elected.len(), 1);
+        assert_eq!(result.elected[0].id, 1);
+    }
+
+    /// Test 09: Plan Routes Votes To Matching Contest
+    #[test]
+    fn test_09_plan_routes_votes_to_matching_contest() {
+        let plan = VotePlan {
+            elections: vec![
+                Election {
+                    id: 1,
+                    description: "Council".to_string(),
+                    choices: vec![
+                        Choice { id: 1, text: "Option A".to_string() },
+                        Choice { id: 2, text: "Option B".to_string() },
+                    ],
+                    seats: 1,
+                    withdrawn: vec![],
+                    tie_strategy: TieStrategy::FirstListed,
+                },
+                Election {
+                    id: 2,
+                    description: "Referendum".to_string(),
+                    choices: vec![
+                        Choice { id: 10, text: "Yes".to_string() },
+                        Choice { id: 11, text: "No".to_string() },
+                    ],
+                    seats: 1,
+                    withdrawn: vec![],
+                    tie_strategy: TieStrategy::FirstListed,
+                },
+            ],
+        };
+
+        let votes = vec![
+            Vote { contest_id: 1, choice_id: 1 },
+            Vote { contest_id: 1, choice_id: 1 },
+            Vote { contest_id: 1, choice_id: 2 },
+            Vote { contest_id: 2, choice_id: 10 },
+            Vote { contest_id: 2, choice_id: 999 }, // invalid choice for contest 2
+        ];
+        let result = tally_plan::<RationalNumber>(&plan, &votes, &[]);
+
+        println!(
+            "\nTest: Plan Routes Votes To Matching Contest\nInput Plan: {}\nInput Votes: {}\nResult: {}\n",
+            serde_json::to_string_pretty(&plan).unwrap(),
+            serde_json::to_string_pretty(&votes).unwrap(),
+            serde_json::to_string_pretty(&result).unwrap(),
+        );
+
+        assert_eq!(result.results.len(), 2);
+        assert_eq!(result.summary.contests.len(), 2);
+
+        let council = result.summary.contests.iter().find(|c| c.contest_id == 1).unwrap();
+        assert_eq!(council.valid_ballots, 3);
+        assert_eq!(council.invalid_ballots, 0);
+        assert_eq!(council.winner.as_ref().unwrap().id, 1);
+
+        let referendum = result.summary.contests.iter().find(|c| c.contest_id == 2).unwrap();
+        assert_eq!(referendum.valid_ballots, 1);
+        assert_eq!(referendum.invalid_ballots, 1);
+    }
+
+    /// Test 10: Withdrawn Choice Takes No FPTP Votes
+    #[test]
+    fn test_10_withdrawn_choice_takes_no_fptp_votes() {
+        let election = Election {
+            id: 1,
+            description: "Withdrawn Candidate".to_string(),
+            choices: vec![
+                Choice { id: 1, text: "Option A".to_string() },
+                Choice { id: 2, text: "Option B".to_string() },
+            ],
+            seats: 1,
+            withdrawn: vec![2],
+            tie_strategy: TieStrategy::FirstListed,
+        };
+
+        let votes = vec![
+            Vote { contest_id: 1, choice_id: 1 },
+            Vote { contest_id: 1, choice_id: 2 },
+            Vote { contest_id: 1, choice_id: 2 },
+        ];
+        let result = tally_votes::<RationalNumber>(&election, &votes);
+
+        println!(
+            "\nTest: Withdrawn Choice Takes No FPTP Votes\nInput Election: {}\nInput Votes: {}\nResult: {}\n",
+            serde_json::to_string_pretty(&election).unwrap(),
+            serde_json::to_string_pretty(&votes).unwrap(),
+            serde_json::to_string_pretty(&result).unwrap(),
+        );
+
+        let withdrawn_result = result.results.iter().find(|r| r.choice_id == 2).unwrap();
+        assert_eq!(withdrawn_result.total_count, RationalNumber::zero());
+        assert_eq!(withdrawn_result.state, ChoiceState::Withdrawn);
+
+        let standing_result = result.results.iter().find(|r| r.choice_id == 1).unwrap();
+        assert_eq!(standing_result.state, ChoiceState::Standing);
+
+        assert_eq!(result.total_votes, 1);
+        assert_eq!(result.winner.unwrap().id, 1);
+    }
+
+    /// Test 11: STV Skips Withdrawn Preferences
+    #[test]
+    fn test_11_stv_skips_withdrawn_preferences() {
+        let election = Election {
+            id: 1,
+            description: "Withdrawn Candidate".to_string(),
+            choices: vec![
+                Choice { id: 1, text: "Option A".to_string() },
+                Choice { id: 2, text: "Option B".to_string() },
+                Choice { id: 3, text: "Option C".to_string() },
+            ],
+            seats: 1,
+            withdrawn: vec![2],
+            tie_strategy: TieStrategy::FirstListed,
+        };
+
+        // Every ballot ranks the withdrawn choice first; it should be
+        // skipped as though never listed, so choice 3 and 1 split the vote.
+        let votes = vec![
+            RankedVote { contest_id: 1, preferences: vec![2, 3] },
+            RankedVote { contest_id: 1, preferences: vec![2, 3] },
+            RankedVote { contest_id: 1, preferences: vec![2, 1] },
+        ];
+        let result = tally_stv::<RationalNumber>(&election, &votes);
+
+        assert!(!result.elected.iter().any(|c| c.id == 2));
+        assert!(result.rounds.iter().all(|r| !r.votes.iter().any(|v| v.choice_id == 2)));
+        assert_eq!(result.elected[0].id, 3);
+    }
+
+    /// Test 12: FPTP Tie Resolved By First Listed
+    #[test]
+    fn test_12_fptp_tie_resolved_by_first_listed() {
+        let election = Election {
+            id: 1,
+            description: "Tied Election".to_string(),
+            choices: vec![
+                Choice { id: 1, text: "Option A".to_string() },
+                Choice { id: 2, text: "Option B".to_string() },
+            ],
+            seats: 1,
+            withdrawn: vec![],
+            tie_strategy: TieStrategy::FirstListed,
+        };
+
+        let votes = vec![
+            Vote { contest_id: 1, choice_id: 1 },
+            Vote { contest_id: 1, choice_id: 2 },
+        ];
+        let result = tally_votes::<RationalNumber>(&election, &votes);
+
+        assert_eq!(result.winner.unwrap().id, 1);
+        let tie_break = result.tie_break.unwrap();
+        assert_eq!(tie_break.tied_choice_ids, vec![1, 2]);
+        assert_eq!(tie_break.resolved_choice_id, 1);
+    }
+
+    /// Test 13: FPTP Tie Resolved Deterministically By Random Strategy
+    #[test]
+    fn test_13_fptp_tie_resolved_deterministically_by_random() {
+        let election = Election {
+            id: 1,
+            description: "Tied Election".to_string(),
+            choices: vec![
+                Choice { id: 1, text: "Option A".to_string() },
+                Choice { id: 2, text: "Option B".to_string() },
+            ],
+            seats: 1,
+            withdrawn: vec![],
+            tie_strategy: TieStrategy::Random { seed: 42 },
+        };
+
+        let votes = vec![
+            Vote { contest_id: 1, choice_id: 1 },
+            Vote { contest_id: 1, choice_id: 2 },
+        ];
+        let first = tally_votes::<RationalNumber>(&election, &votes);
+        let second = tally_votes::<RationalNumber>(&election, &votes);
+
+        assert_eq!(first.winner.unwrap().id, second.winner.unwrap().id);
+        assert_eq!(
+            first.tie_break.unwrap().resolved_choice_id,
+            second.tie_break.unwrap().resolved_choice_id
+        );
+    }
+
+    /// Test 14: STV Elimination Tie Broken By First Listed
+    #[test]
+    fn test_14_stv_elimination_tie_broken_by_first_listed() {
+        let election = Election {
+            id: 1,
+            description: "Three Way".to_string(),
+            choices: vec![
+                Choice { id: 1, text: "Option A".to_string() },
+                Choice { id: 2, text: "Option B".to_string() },
+                Choice { id: 3, text: "Option C".to_string() },
+            ],
+            seats: 1,
+            withdrawn: vec![],
+            tie_strategy: TieStrategy::FirstListed,
+        };
+
+        // A leads with 2 first preferences; B and C tie for last with 1
+        // each, and neither reaches the quota of 3, so round one must
+        // break a tie to decide who is eliminated.
+        let votes = vec![
+            RankedVote { contest_id: 1, preferences: vec![1] },
+            RankedVote { contest_id: 1, preferences: vec![1] },
+            RankedVote { contest_id: 1, preferences: vec![2, 1] },
+            RankedVote { contest_id: 1, preferences: vec![3, 1] },
+        ];
+        let result = tally_stv::<RationalNumber>(&election, &votes);
+
+        let first_round = &result.rounds[0];
+        let tie_break = first_round.tie_break.as_ref().unwrap();
+        assert_eq!(tie_break.tied_choice_ids, vec![2, 3]);
+        assert_eq!(tie_break.resolved_choice_id, 3);
+        assert_eq!(first_round.eliminated, Some(3));
+        assert_eq!(result.elected[0].id, 1);
+    }
+
+    /// Test 15: CSV Output Includes A Trailing Winner Row
+    #[test]
+    fn test_15_csv_output_includes_winner_row() {
+        let plan = VotePlan {
+            elections: vec![Election {
+                id: 1,
+                description: "Council".to_string(),
+                choices: vec![
+                    Choice { id: 1, text: "Option A".to_string() },
+                    Choice { id: 2, text: "Option B".to_string() },
+                ],
+                seats: 1,
+                withdrawn: vec![],
+                tie_strategy: TieStrategy::FirstListed,
+            }],
+        };
+
+        let votes = vec![
+            Vote { contest_id: 1, choice_id: 1 },
+            Vote { contest_id: 1, choice_id: 1 },
+            Vote { contest_id: 1, choice_id: 2 },
+        ];
+        let result = tally_plan::<RationalNumber>(&plan, &votes, &[]);
+        let csv_bytes = render_csv(&plan, &result).unwrap();
+        let csv = String::from_utf8(csv_bytes).unwrap();
+        let mut lines = csv.lines();
+
+        assert_eq!(lines.next().unwrap(), "contest_id,choice_id,choice_text,total_count");
+        assert_eq!(lines.next().unwrap(), "1,1,Option A,2/1");
+        assert_eq!(lines.next().unwrap(), "1,2,Option B,1/1");
+        assert_eq!(lines.next().unwrap(), "1,1,Option A,2/1");
+        assert!(lines.next().is_none());
+    }
+
+    /// Test 16: STV Logs A Round When Remaining Seats Are Filled Without A
+    /// Quota Or An Elimination
+    #[test]
+    fn test_16_stv_logs_round_when_filling_remaining_seats() {
+        let election = Election {
+            id: 1,
+            description: "Two Seats, Two Candidates".to_string(),
+            choices: vec![
+                Choice { id: 1, text: "Option A".to_string() },
+                Choice { id: 2, text: "Option B".to_string() },
+            ],
+            seats: 2,
+            withdrawn: vec![],
+            tie_strategy: TieStrategy::FirstListed,
+        };
+
+        let votes = vec![
+            RankedVote { contest_id: 1, preferences: vec![1] },
+            RankedVote { contest_id: 1, preferences: vec![2] },
+        ];
+        let result = tally_stv::<RationalNumber>(&election, &votes);
+
+        assert_eq!(result.elected.len(), 2);
+        assert_eq!(result.rounds.len(), 1);
+        assert_eq!(result.rounds[0].elected, vec![1, 2]);
+        assert!(result.rounds[0].eliminated.is_none());
+        assert_eq!(result.rounds[0].votes.len(), 2);
+    }
+
+    /// Test 17: Plan Routes Multi-Seat Contests To STV
+    #[test]
+    fn test_17_plan_routes_multi_seat_contests_to_stv() {
+        let plan = VotePlan {
+            elections: vec![
+                Election {
+                    id: 1,
+                    description: "Mayor".to_string(),
+                    choices: vec![
+                        Choice { id: 1, text: "Option A".to_string() },
+                        Choice { id: 2, text: "Option B".to_string() },
+                    ],
+                    seats: 1,
+                    withdrawn: vec![],
+                    tie_strategy: TieStrategy::FirstListed,
+                },
+                Election {
+                    id: 2,
+                    description: "Council".to_string(),
+                    choices: vec![
+                        Choice { id: 10, text: "Option C".to_string() },
+                        Choice { id: 11, text: "Option D".to_string() },
+                    ],
+                    seats: 2,
+                    withdrawn: vec![],
+                    tie_strategy: TieStrategy::FirstListed,
+                },
+            ],
+        };
+
+        let votes = vec![Vote { contest_id: 1, choice_id: 1 }, Vote { contest_id: 1, choice_id: 2 }];
+        let ranked_votes = vec![
+            RankedVote { contest_id: 2, preferences: vec![10] },
+            RankedVote { contest_id: 2, preferences: vec![11] },
+        ];
+        let result = tally_plan::<RationalNumber>(&plan, &votes, &ranked_votes);
+
+        assert_eq!(result.results.len(), 1);
+        assert_eq!(result.results[0].contest_id, 1);
+        assert_eq!(result.stv_results.len(), 1);
+        assert_eq!(result.stv_results[0].contest_id, 2);
+        assert_eq!(result.stv_results[0].elected.len(), 2);
+    }
+
+    /// Test 18: STV Surplus Transfer Only Moves Ballots Truly Held By The
+    /// Elected Candidate
+    ///
+    /// A ballot that lists the just-elected candidate *later* in its
+    /// preferences (but is currently held by a different continuing
+    /// candidate) must not be swept into that candidate's surplus transfer
+    /// just because the candidate's ID appears somewhere on the ballot.
+    #[test]
+    fn test_18_stv_surplus_transfer_ignores_non_current_ballots() {
+        let election = Election {
+            id: 1,
+            description: "Two Seats, Three Candidates".to_string(),
+            choices: vec![
+                Choice { id: 1, text: "Option A".to_string() },
+                Choice { id: 2, text: "Option B".to_string() },
+                Choice { id: 3, text: "Option C".to_string() },
+            ],
+            seats: 2,
+            withdrawn: vec![],
+            tie_strategy: TieStrategy::FirstListed,
+        };
+
+        // Quota = floor(10/3) + 1 = 4. Choice 1 starts above quota on the
+        // 6 ballots that truly rank it first; the 3 ballots [2, 1] are
+        // currently held by choice 2 and must stay there even though they
+        // list choice 1 second.
+        let mut votes = vec![RankedVote { contest_id: 1, preferences: vec![2, 1] }; 3];
+        votes.extend(vec![RankedVote { contest_id: 1, preferences: vec![1, 3] }; 6]);
+        votes.push(RankedVote { contest_id: 1, preferences: vec![3] });
+
+        let result = tally_stv::<RationalNumber>(&election, &votes);
+
+        assert_eq!(result.rounds[0].votes.iter().find(|c| c.choice_id == 2).unwrap().total_count, RationalNumber::parse("3").unwrap());
+        assert!(result.rounds[0].transfers.iter().all(|t| t.to_choice_id != 2));
+        assert_eq!(result.elected.len(), 2);
+        assert!(result.elected.iter().any(|c| c.id == 1));
+        assert!(result.elected.iter().any(|c| c.id == 2));
+    }
+
+    /// Test 19: CSV Rendering Matches Elections To Results By Contest ID,
+    /// Not List Position
+    ///
+    /// `results.results` only holds FPTP contests (STV ones go to
+    /// `stv_results`), so a plan that lists its STV contest before its FPTP
+    /// one must not misalign `render_csv`'s lookup of choice text.
+    #[test]
+    fn test_19_csv_matches_elections_to_results_by_contest_id() {
+        let plan = VotePlan {
+            elections: vec![
+                Election {
+                    id: 2,
+                    description: "Council".to_string(),
+                    choices: vec![
+                        Choice { id: 10, text: "Option C".to_string() },
+                        Choice { id: 11, text: "Option D".to_string() },
+                    ],
+                    seats: 2,
+                    withdrawn: vec![],
+                    tie_strategy: TieStrategy::FirstListed,
+                },
+                Election {
+                    id: 1,
+                    description: "Mayor".to_string(),
+                    choices: vec![
+                        Choice { id: 1, text: "Option A".to_string() },
+                        Choice { id: 2, text: "Option B".to_string() },
+                    ],
+                    seats: 1,
+                    withdrawn: vec![],
+                    tie_strategy: TieStrategy::FirstListed,
+                },
+            ],
+        };
+
+        let votes = vec![Vote { contest_id: 1, choice_id: 1 }, Vote { contest_id: 1, choice_id: 2 }];
+        let ranked_votes = vec![
+            RankedVote { contest_id: 2, preferences: vec![10] },
+            RankedVote { contest_id: 2, preferences: vec![11] },
+        ];
+        let result = tally_plan::<RationalNumber>(&plan, &votes, &ranked_votes);
+        let csv_bytes = render_csv(&plan, &result).unwrap();
+        let csv = String::from_utf8(csv_bytes).unwrap();
+
+        assert!(csv.contains("1,1,Option A,1/1"));
+        assert!(csv.contains("1,2,Option B,1/1"));
+        assert!(!csv.contains(",,"));
+    }
 }
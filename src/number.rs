@@ -0,0 +1,325 @@
+use std::fmt;
+use std::ops::{Add, Div, Mul, Sub};
+
+use num_bigint::BigInt;
+use num_rational::BigRational;
+use num_traits::{One, Zero as NumZero};
+use serde::de::{self, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// A numeric value exact enough for vote tallying: STV surplus transfers
+/// repeatedly add, subtract, multiply and divide vote weights, and doing
+/// that in `f64` silently accumulates rounding error that can flip a close
+/// result. Implementors of this trait give the tallying functions an exact
+/// arithmetic to run on, chosen by the caller.
+pub trait Number:
+    Clone
+    + PartialEq
+    + PartialOrd
+    + fmt::Debug
+    + fmt::Display
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Serialize
+    + for<'de> Deserialize<'de>
+{
+    /// The additive identity.
+    fn zero() -> Self;
+
+    /// Parse a value from its textual form: an integer or a decimal such as
+    /// `"1.5"`. `RationalNumber` also accepts a `"numerator/denominator"`
+    /// pair; `FixedPoint` does not.
+    fn parse(s: &str) -> Result<Self, String>;
+}
+
+/// An arbitrary-precision rational number, used when the jurisdiction's
+/// counting rules require exact fractions with no rounding at all.
+#[derive(Clone, Debug, PartialEq, PartialOrd)]
+pub struct RationalNumber(BigRational);
+
+impl RationalNumber {
+    fn from_ratio(numer: BigInt, denom: BigInt) -> Self {
+        RationalNumber(BigRational::new(numer, denom))
+    }
+}
+
+impl Number for RationalNumber {
+    fn zero() -> Self {
+        RationalNumber(BigRational::zero())
+    }
+
+    fn parse(s: &str) -> Result<Self, String> {
+        let s = s.trim();
+        if let Some((numer, denom)) = s.split_once('/') {
+            let numer: BigInt = numer
+                .trim()
+                .parse()
+                .map_err(|e| format!("invalid numerator {numer:?}: {e}"))?;
+            let denom: BigInt = denom
+                .trim()
+                .parse()
+                .map_err(|e| format!("invalid denominator {denom:?}: {e}"))?;
+            Ok(RationalNumber::from_ratio(numer, denom))
+        } else if let Some(dot) = s.find('.') {
+            let decimals = (s.len() - dot - 1) as u32;
+            let digits: String = s.chars().filter(|c| *c != '.').collect();
+            let numer: BigInt = digits
+                .parse()
+                .map_err(|e| format!("invalid decimal {s:?}: {e}"))?;
+            let denom = BigInt::from(10).pow(decimals);
+            Ok(RationalNumber::from_ratio(numer, denom))
+        } else {
+            let numer: BigInt = s.parse().map_err(|e| format!("invalid integer {s:?}: {e}"))?;
+            Ok(RationalNumber::from_ratio(numer, BigInt::one()))
+        }
+    }
+}
+
+impl Add for RationalNumber {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        RationalNumber(self.0 + rhs.0)
+    }
+}
+
+impl Sub for RationalNumber {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self {
+        RationalNumber(self.0 - rhs.0)
+    }
+}
+
+impl Mul for RationalNumber {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self {
+        RationalNumber(self.0 * rhs.0)
+    }
+}
+
+impl Div for RationalNumber {
+    type Output = Self;
+    fn div(self, rhs: Self) -> Self {
+        RationalNumber(self.0 / rhs.0)
+    }
+}
+
+impl fmt::Display for RationalNumber {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.0.numer(), self.0.denom())
+    }
+}
+
+impl Serialize for RationalNumber {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for RationalNumber {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct RationalVisitor;
+        impl Visitor<'_> for RationalVisitor {
+            type Value = RationalNumber;
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("a number, decimal string, or \"numerator/denominator\" string")
+            }
+            fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+                RationalNumber::parse(v).map_err(de::Error::custom)
+            }
+            fn visit_i64<E: de::Error>(self, v: i64) -> Result<Self::Value, E> {
+                RationalNumber::parse(&v.to_string()).map_err(de::Error::custom)
+            }
+            fn visit_u64<E: de::Error>(self, v: u64) -> Result<Self::Value, E> {
+                RationalNumber::parse(&v.to_string()).map_err(de::Error::custom)
+            }
+            fn visit_f64<E: de::Error>(self, v: f64) -> Result<Self::Value, E> {
+                RationalNumber::parse(&v.to_string()).map_err(de::Error::custom)
+            }
+        }
+        deserializer.deserialize_any(RationalVisitor)
+    }
+}
+
+/// A fixed-point number that rounds every intermediate add/sub/mul/div to
+/// `DECIMALS` places after the decimal point, truncating toward zero,
+/// matching jurisdictions whose rules mandate a fixed rounding step at each
+/// transfer rather than exact fractions.
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+pub struct FixedPoint<const DECIMALS: u32> {
+    /// The value multiplied by `10^DECIMALS`, truncated toward zero.
+    scaled: i128,
+}
+
+impl<const DECIMALS: u32> FixedPoint<DECIMALS> {
+    fn scale() -> i128 {
+        10i128.pow(DECIMALS)
+    }
+
+    fn from_scaled(scaled: i128) -> Self {
+        FixedPoint { scaled }
+    }
+}
+
+impl<const DECIMALS: u32> Number for FixedPoint<DECIMALS> {
+    fn zero() -> Self {
+        FixedPoint { scaled: 0 }
+    }
+
+    fn parse(s: &str) -> Result<Self, String> {
+        let s = s.trim();
+        let (sign, s) = match s.strip_prefix('-') {
+            Some(rest) => (-1i128, rest),
+            None => (1i128, s),
+        };
+        let (int_part, frac_part) = match s.split_once('.') {
+            Some((i, f)) => (i, f),
+            None => (s, ""),
+        };
+        let int_part: i128 = if int_part.is_empty() {
+            0
+        } else {
+            int_part
+                .parse()
+                .map_err(|e| format!("invalid integer part {int_part:?}: {e}"))?
+        };
+        let mut frac_digits = frac_part.to_string();
+        frac_digits.truncate(DECIMALS as usize);
+        while frac_digits.len() < DECIMALS as usize {
+            frac_digits.push('0');
+        }
+        let frac_value: i128 = if frac_digits.is_empty() {
+            0
+        } else {
+            frac_digits
+                .parse()
+                .map_err(|e| format!("invalid fractional part {frac_part:?}: {e}"))?
+        };
+        Ok(FixedPoint::from_scaled(sign * (int_part * Self::scale() + frac_value)))
+    }
+}
+
+impl<const DECIMALS: u32> Add for FixedPoint<DECIMALS> {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        FixedPoint::from_scaled(self.scaled + rhs.scaled)
+    }
+}
+
+impl<const DECIMALS: u32> Sub for FixedPoint<DECIMALS> {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self {
+        FixedPoint::from_scaled(self.scaled - rhs.scaled)
+    }
+}
+
+impl<const DECIMALS: u32> Mul for FixedPoint<DECIMALS> {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self {
+        FixedPoint::from_scaled(self.scaled * rhs.scaled / Self::scale())
+    }
+}
+
+impl<const DECIMALS: u32> Div for FixedPoint<DECIMALS> {
+    type Output = Self;
+    fn div(self, rhs: Self) -> Self {
+        FixedPoint::from_scaled(self.scaled * Self::scale() / rhs.scaled)
+    }
+}
+
+impl<const DECIMALS: u32> fmt::Display for FixedPoint<DECIMALS> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let scale = Self::scale();
+        // Derived from `self.scaled` directly, not from `whole`: when the
+        // value is a negative fraction (e.g. -0.50), `whole` truncates to
+        // 0 and loses the sign if read off of it instead.
+        let sign = if self.scaled < 0 { "-" } else { "" };
+        let whole = (self.scaled / scale).abs();
+        let frac = (self.scaled % scale).abs();
+        if DECIMALS == 0 {
+            write!(f, "{sign}{whole}")
+        } else {
+            write!(f, "{sign}{whole}.{frac:0width$}", width = DECIMALS as usize)
+        }
+    }
+}
+
+impl<const DECIMALS: u32> Serialize for FixedPoint<DECIMALS> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+impl<'de, const DECIMALS: u32> Deserialize<'de> for FixedPoint<DECIMALS> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct FixedPointVisitor<const DECIMALS: u32>;
+        impl<const DECIMALS: u32> Visitor<'_> for FixedPointVisitor<DECIMALS> {
+            type Value = FixedPoint<DECIMALS>;
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("a number or decimal string")
+            }
+            fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+                FixedPoint::parse(v).map_err(de::Error::custom)
+            }
+            fn visit_i64<E: de::Error>(self, v: i64) -> Result<Self::Value, E> {
+                FixedPoint::parse(&v.to_string()).map_err(de::Error::custom)
+            }
+            fn visit_u64<E: de::Error>(self, v: u64) -> Result<Self::Value, E> {
+                FixedPoint::parse(&v.to_string()).map_err(de::Error::custom)
+            }
+            fn visit_f64<E: de::Error>(self, v: f64) -> Result<Self::Value, E> {
+                FixedPoint::parse(&v.to_string()).map_err(de::Error::custom)
+            }
+        }
+        deserializer.deserialize_any(FixedPointVisitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rational_add_sub_mul_div() {
+        let a = RationalNumber::parse("1/2").unwrap();
+        let b = RationalNumber::parse("1/3").unwrap();
+        assert_eq!((a.clone() + b.clone()).to_string(), "5/6");
+        assert_eq!((a.clone() - b.clone()).to_string(), "1/6");
+        assert_eq!((a.clone() * b.clone()).to_string(), "1/6");
+        assert_eq!((a / b).to_string(), "3/2");
+    }
+
+    #[test]
+    fn rational_parses_decimal_and_integer() {
+        assert_eq!(RationalNumber::parse("1.5").unwrap().to_string(), "3/2");
+        assert_eq!(RationalNumber::parse("4").unwrap().to_string(), "4/1");
+    }
+
+    #[test]
+    fn fixed_point_rounds_to_configured_decimals() {
+        type Fp = FixedPoint<2>;
+        let a = Fp::parse("1.005").unwrap();
+        assert_eq!(a.to_string(), "1.00");
+        let surplus = Fp::parse("10.00").unwrap();
+        let total = Fp::parse("3.00").unwrap();
+        let fraction = surplus / total;
+        assert_eq!(fraction.to_string(), "3.33");
+    }
+
+    #[test]
+    fn fixed_point_zero_decimals_behaves_like_integer() {
+        type Fp = FixedPoint<0>;
+        let a = Fp::parse("7").unwrap();
+        let b = Fp::parse("2").unwrap();
+        assert_eq!((a / b).to_string(), "3");
+    }
+
+    #[test]
+    fn fixed_point_keeps_sign_when_whole_part_is_zero() {
+        type Fp = FixedPoint<2>;
+        let a = Fp::parse("0.00").unwrap();
+        let b = Fp::parse("0.50").unwrap();
+        assert_eq!((a - b).to_string(), "-0.50");
+    }
+}
@@ -0,0 +1,219 @@
+//! Reads and writes the BLT ballot file format used by tools like OpenSTV,
+//! so this crate can ingest and export ballots produced elsewhere.
+//!
+//! A BLT file looks like:
+//!
+//! ```text
+//! 4 2
+//! -3
+//! 1 1 3 0
+//! 2 4 1 0
+//! 1 2 0
+//! 0
+//! "Alice"
+//! "Bob"
+//! "Carol"
+//! "Dave"
+//! "Example Election"
+//! ```
+//!
+//! The header line is `<num_candidates> <num_seats>`. An optional line of
+//! negative integers lists withdrawn candidate numbers. Each ballot is a
+//! line of the form `<weight> <pref1> <pref2> ... 0`, and the list of
+//! ballots is terminated by a lone `0` line. Then come `num_candidates`
+//! quoted candidate names, and finally the quoted election title.
+
+use crate::number::Number;
+use crate::{Choice, Election, RankedVote};
+
+/// One ranked ballot as read from (or to be written to) a BLT file.
+pub(crate) struct BltBallot<N: Number> {
+    pub(crate) weight: N,
+    pub(crate) preferences: Vec<u32>,
+}
+
+/// The result of parsing a BLT file: the election it describes (including
+/// any withdrawn candidates, recorded on `election.withdrawn`) and the
+/// ballots cast.
+pub(crate) struct BltFile<N: Number> {
+    pub(crate) election: Election,
+    pub(crate) ballots: Vec<BltBallot<N>>,
+}
+
+/// Parse a BLT file's contents into an `Election` and its ballots.
+///
+/// - `input`: The raw contents of a `.blt` file.
+/// - `contest_id`: BLT has no notion of a contest ID, so the caller
+///   supplies one to assign to the resulting `Election`.
+pub(crate) fn parse_blt<N: Number>(input: &str, contest_id: u32) -> Result<BltFile<N>, String> {
+    let mut lines = input.lines().map(str::trim).filter(|l| !l.is_empty());
+
+    let header = lines.next().ok_or("BLT file is empty: missing header line")?;
+    let mut header_parts = header.split_whitespace();
+    let num_candidates: u32 = header_parts
+        .next()
+        .ok_or("missing candidate count in header")?
+        .parse()
+        .map_err(|e| format!("invalid candidate count: {e}"))?;
+    let seats: usize = header_parts
+        .next()
+        .ok_or("missing seat count in header")?
+        .parse()
+        .map_err(|e| format!("invalid seat count: {e}"))?;
+
+    let mut line = lines.next().ok_or("BLT file ends after header")?;
+
+    let mut withdrawn = Vec::new();
+    if line.split_whitespace().all(|tok| tok.starts_with('-')) {
+        withdrawn = line
+            .split_whitespace()
+            .map(|tok| {
+                tok.trim_start_matches('-')
+                    .parse::<u32>()
+                    .map_err(|e| format!("invalid withdrawn candidate {tok:?}: {e}"))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        line = lines.next().ok_or("BLT file ends after withdrawn line")?;
+    }
+
+    let mut ballots = Vec::new();
+    loop {
+        let tokens: Vec<&str> = line.split_whitespace().collect();
+        if tokens == ["0"] {
+            break;
+        }
+        let weight = N::parse(tokens.first().ok_or("ballot line is empty")?)?;
+        let preferences: Vec<u32> = tokens[1..]
+            .iter()
+            .take_while(|t| **t != "0")
+            .map(|t| t.parse::<u32>().map_err(|e| format!("invalid preference {t:?}: {e}")))
+            .collect::<Result<Vec<_>, _>>()?;
+        ballots.push(BltBallot { weight, preferences });
+
+        line = lines.next().ok_or("BLT file ends before the ballot-terminating \"0\" line")?;
+    }
+
+    let mut names = Vec::new();
+    for _ in 0..num_candidates {
+        let name_line = lines.next().ok_or("BLT file has fewer candidate names than declared")?;
+        names.push(unquote(name_line)?);
+    }
+
+    let title_line = lines.next().ok_or("BLT file is missing its title line")?;
+    let title = unquote(title_line)?;
+
+    let choices = names
+        .into_iter()
+        .enumerate()
+        .map(|(i, text)| Choice { id: (i + 1) as u32, text })
+        .collect();
+
+    Ok(BltFile {
+        election: Election {
+            id: contest_id,
+            description: title,
+            choices,
+            seats,
+            withdrawn,
+            // BLT has no notion of tie-breaking, so fall back to the
+            // same default an election file would if it omitted the field.
+            tie_strategy: crate::TieStrategy::FirstListed,
+        },
+        ballots,
+    })
+}
+
+fn unquote(line: &str) -> Result<String, String> {
+    line.strip_prefix('"')
+        .and_then(|s| s.strip_suffix('"'))
+        .map(str::to_string)
+        .ok_or_else(|| format!("expected a quoted string, got {line:?}"))
+}
+
+/// Serialize an election and its ranked ballots back to the BLT format.
+pub(crate) fn write_blt<N: Number>(election: &Election, ballots: &[BltBallot<N>]) -> String {
+    let mut out = String::new();
+    out.push_str(&format!("{} {}\n", election.choices.len(), election.seats));
+
+    if !election.withdrawn.is_empty() {
+        let withdrawn_line = election.withdrawn.iter().map(|id| format!("-{id}")).collect::<Vec<_>>().join(" ");
+        out.push_str(&withdrawn_line);
+        out.push('\n');
+    }
+
+    for ballot in ballots {
+        let prefs = ballot.preferences.iter().map(|p| p.to_string()).collect::<Vec<_>>().join(" ");
+        out.push_str(&format!("{} {} 0\n", ballot.weight, prefs));
+    }
+    out.push_str("0\n");
+
+    for choice in &election.choices {
+        out.push_str(&format!("\"{}\"\n", choice.text));
+    }
+    out.push_str(&format!("\"{}\"\n", election.description));
+
+    out
+}
+
+/// Convert parsed BLT ballots into the crate's `RankedVote` representation,
+/// for routing through `tally_stv`. Ballot weight is dropped in this
+/// conversion, since `RankedVote` has no weight field of its own.
+pub(crate) fn ranked_votes(contest_id: u32, ballots: &[BltBallot<impl Number>]) -> Vec<RankedVote> {
+    ballots
+        .iter()
+        .map(|b| RankedVote { contest_id, preferences: b.preferences.clone() })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::number::RationalNumber;
+
+    const SAMPLE: &str = "4 2\n-3\n1 1 3 0\n2 4 1 0\n1 2 0\n0\n\"Alice\"\n\"Bob\"\n\"Carol\"\n\"Dave\"\n\"Example Election\"\n";
+
+    #[test]
+    fn parses_header_withdrawn_ballots_and_names() {
+        let parsed = parse_blt::<RationalNumber>(SAMPLE, 7).unwrap();
+
+        assert_eq!(parsed.election.id, 7);
+        assert_eq!(parsed.election.seats, 2);
+        assert_eq!(parsed.election.description, "Example Election");
+        assert_eq!(parsed.election.choices.len(), 4);
+        assert_eq!(parsed.election.choices[0].text, "Alice");
+        assert_eq!(parsed.election.withdrawn, vec![3]);
+
+        assert_eq!(parsed.ballots.len(), 3);
+        assert_eq!(parsed.ballots[0].preferences, vec![1, 3]);
+        assert_eq!(parsed.ballots[1].weight, RationalNumber::parse("2").unwrap());
+    }
+
+    #[test]
+    fn round_trips_through_write_and_parse() {
+        let parsed = parse_blt::<RationalNumber>(SAMPLE, 7).unwrap();
+        let written = write_blt(&parsed.election, &parsed.ballots);
+        let reparsed = parse_blt::<RationalNumber>(&written, 7).unwrap();
+
+        assert_eq!(reparsed.election.description, parsed.election.description);
+        assert_eq!(reparsed.election.choices.len(), parsed.election.choices.len());
+        assert_eq!(reparsed.election.withdrawn, parsed.election.withdrawn);
+        assert_eq!(reparsed.ballots.len(), parsed.ballots.len());
+        assert_eq!(reparsed.ballots[0].preferences, parsed.ballots[0].preferences);
+    }
+
+    #[test]
+    fn rejects_truncated_file() {
+        let truncated = "2 1\n1 1 0\n";
+        assert!(parse_blt::<RationalNumber>(truncated, 1).is_err());
+    }
+
+    #[test]
+    fn converts_ballots_to_ranked_votes() {
+        let parsed = parse_blt::<RationalNumber>(SAMPLE, 7).unwrap();
+        let votes = ranked_votes(parsed.election.id, &parsed.ballots);
+
+        assert_eq!(votes.len(), parsed.ballots.len());
+        assert!(votes.iter().all(|v| v.contest_id == 7));
+        assert_eq!(votes[0].preferences, parsed.ballots[0].preferences);
+    }
+}